@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fs;
+
+use serde::Deserialize;
+
+/// A saved Gmail search the user can jump to without restarting, analogous to
+/// himalaya's named accounts/folders but scoped to a single search query.
+#[derive(Debug, Clone, Deserialize)]
+pub struct View {
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub sort: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_page_size")]
+    pub page_size: u32,
+    #[serde(default = "default_views")]
+    pub views: Vec<View>,
+}
+
+fn default_page_size() -> u32 {
+    10
+}
+
+fn default_views() -> Vec<View> {
+    vec![View {
+        name: "unread".to_string(),
+        query: "is:unread".to_string(),
+        sort: None,
+    }]
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            page_size: default_page_size(),
+            views: default_views(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads `path` if it exists, falling back to defaults when the file is
+    /// simply missing. A malformed config, or one with an explicit empty
+    /// `views = []` (which `#[serde(default = "default_views")]` does not
+    /// catch, since that default only fires when the key is absent), is
+    /// still reported as an error rather than handed back to a caller that
+    /// indexes `views` unconditionally.
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let config: Self = match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)?,
+            Err(_) => Self::default(),
+        };
+
+        if config.views.is_empty() {
+            return Err("config.toml must define at least one view".into());
+        }
+
+        Ok(config)
+    }
+}
@@ -1,22 +1,33 @@
 use std::error::Error;
+use std::fmt;
 use yup_oauth2::AccessToken;
 use serde::{Deserialize, Serialize};
 use base64::{Engine as _, engine::general_purpose};
 use html2text::from_read;
-use pulldown_cmark::{Parser, html};
+use encoding_rs::{Encoding, UTF_8};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Email {
     pub id: String,
     pub subject: String,
     pub body: String,
+    pub html_body: Option<String>,
     pub unsubscribe_link: Option<String>,
+    pub unsubscribe_one_click: bool,
+    pub received_at: Option<i64>,
+    pub starred: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct GmailMessage {
     id: String,
     payload: Payload,
+    #[serde(default)]
+    #[serde(rename = "internalDate")]
+    internal_date: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "labelIds")]
+    label_ids: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,6 +48,8 @@ struct Header {
 #[derive(Debug, Serialize, Deserialize)]
 struct Part {
     mimeType: String,
+    #[serde(default)]
+    headers: Vec<Header>,
     body: Body,
     parts: Option<Vec<Part>>,
 }
@@ -48,6 +61,46 @@ struct Body {
     size: Option<i32>,
 }
 
+/// Distinguishes connectivity/server hiccups worth retrying from auth failures
+/// that won't resolve by trying again (bad `client_secret.json`, revoked token).
+#[derive(Debug)]
+pub enum FetchError {
+    Transient(String),
+    Fatal(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Transient(message) => write!(f, "{}", message),
+            FetchError::Fatal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+fn classify_reqwest_error(e: &reqwest::Error) -> FetchError {
+    match e.status() {
+        Some(status) if status.as_u16() == 401 || status.as_u16() == 403 => {
+            FetchError::Fatal(format!("Authentication failed ({})", status))
+        }
+        _ => FetchError::Transient(e.to_string()),
+    }
+}
+
+/// Orders `emails` by `received_at`, newest-first unless `sort` is
+/// `date_asc`. Shared between `fetch_page` (sorting a single page) and
+/// callers that accumulate multiple pages (re-sorting the full list so
+/// pagination doesn't produce a sawtooth ordering).
+pub fn sort_emails(emails: &mut [Email], sort: Option<&str>) {
+    if sort == Some("date_asc") {
+        emails.sort_by_key(|e| e.received_at.unwrap_or(0));
+    } else {
+        emails.sort_by_key(|e| std::cmp::Reverse(e.received_at.unwrap_or(0)));
+    }
+}
+
 pub struct GmailClient {
     client: reqwest::Client,
     token: AccessToken,
@@ -61,39 +114,87 @@ impl GmailClient {
         }
     }
 
-    pub async fn fetch_emails(&self) -> Result<Vec<Email>, Box<dyn Error>> {
-        let url = "https://www.googleapis.com/gmail/v1/users/me/messages?q=is:unread&maxResults=10";
-        let response: serde_json::Value = self.client.get(url)
-            .bearer_auth(self.token.token().ok_or("No token available")?)
+    /// GETs `url` with `params`, classifying the outcome as a `FetchError` so
+    /// callers can tell a retryable hiccup from a fatal auth failure.
+    async fn get_json(&self, url: &str, params: &[(&str, String)]) -> Result<serde_json::Value, FetchError> {
+        let token = self.token.token()
+            .ok_or_else(|| FetchError::Fatal("No access token available".to_string()))?;
+
+        let response = self.client.get(url)
+            .bearer_auth(token)
+            .query(params)
             .send()
-            .await?
-            .json()
-            .await?;
+            .await
+            .map_err(|e| classify_reqwest_error(&e))?;
 
-        let messages = response["messages"].as_array()
-            .ok_or("No messages found")?;
+        let status = response.status();
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(FetchError::Fatal(format!("Authentication failed ({})", status)));
+        }
+        if !status.is_success() {
+            return Err(FetchError::Transient(format!("Gmail API returned {}", status)));
+        }
 
-        let mut emails = Vec::new();
+        response.json().await.map_err(|e| FetchError::Transient(e.to_string()))
+    }
+
+    /// Fetches one page of `query` results, returning the emails alongside the
+    /// `nextPageToken` (if any) so the caller can continue with another call.
+    /// `sort` selects `date_asc` or defaults to newest-first. This only orders
+    /// the emails within this one page — a caller accumulating multiple pages
+    /// must call `sort_emails` again over the full accumulated list, since
+    /// each page's results are independently sorted relative to each other.
+    pub async fn fetch_page(
+        &self,
+        query: &str,
+        max_results: u32,
+        page_token: Option<&str>,
+        sort: Option<&str>,
+    ) -> Result<(Vec<Email>, Option<String>), FetchError> {
+        let url = "https://www.googleapis.com/gmail/v1/users/me/messages";
+        let mut params = vec![
+            ("q", query.to_string()),
+            ("maxResults", max_results.to_string()),
+        ];
+        if let Some(token) = page_token {
+            params.push(("pageToken", token.to_string()));
+        }
+
+        let response = self.get_json(url, &params).await?;
+
+        let next_page_token = response["nextPageToken"].as_str().map(|s| s.to_string());
 
+        let messages = match response["messages"].as_array() {
+            Some(messages) => messages,
+            None => return Ok((Vec::new(), next_page_token)),
+        };
+
+        let mut emails = Vec::new();
         for message in messages {
-            let id = message["id"].as_str().ok_or("No id found")?;
+            let id = message["id"].as_str()
+                .ok_or_else(|| FetchError::Transient("Message missing id".to_string()))?;
             let email = self.fetch_email(id).await?;
             emails.push(email);
         }
 
-        Ok(emails)
+        sort_emails(&mut emails, sort);
+
+        Ok((emails, next_page_token))
     }
 
-    async fn fetch_email(&self, id: &str) -> Result<Email, Box<dyn Error>> {
+    /// Fetches and parses a single message. Routed through `get_json` (rather
+    /// than a bare `send`/`json`) so a 401/403 here — which, in practice, is
+    /// where a revoked token actually surfaces, since this is called once per
+    /// message from the `fetch_page` hot path — is classified `Fatal` instead
+    /// of being flattened into an endlessly-retried `Transient` error.
+    async fn fetch_email(&self, id: &str) -> Result<Email, FetchError> {
         let url = format!("https://www.googleapis.com/gmail/v1/users/me/messages/{}", id);
-        let response: GmailMessage = self.client.get(&url)
-            .bearer_auth(self.token.token().ok_or("No token available")?)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let response = self.get_json(&url, &[]).await?;
+        let message: GmailMessage = serde_json::from_value(response)
+            .map_err(|e| FetchError::Transient(e.to_string()))?;
 
-        self.parse_message(response)
+        self.parse_message(message)
+            .map_err(|e| FetchError::Transient(e.to_string()))
     }
 
     fn parse_message(&self, msg: GmailMessage) -> Result<Email, Box<dyn Error>> {
@@ -103,17 +204,34 @@ impl GmailClient {
             .unwrap_or_default();
 
         let unsubscribe_link = self.extract_unsubscribe_link(&msg.payload.headers);
+        let unsubscribe_one_click = self.extract_unsubscribe_one_click(&msg.payload.headers);
 
-        let body = self.extract_body(&msg.payload)?;
+        let body = self.extract_body(&msg.payload);
+        let html_body = self.extract_html(&msg.payload);
+        let received_at = msg.internal_date.as_deref().and_then(|s| s.parse::<i64>().ok());
+        let starred = msg.label_ids.iter().any(|l| l == "STARRED");
 
         Ok(Email {
             id: msg.id,
             subject,
             body,
+            html_body,
             unsubscribe_link,
+            unsubscribe_one_click,
+            received_at,
+            starred,
         })
     }
 
+    /// RFC 8058: one-click unsubscribe is only safe to automate when the message
+    /// advertises `List-Unsubscribe=One-Click` in `List-Unsubscribe-Post`.
+    fn extract_unsubscribe_one_click(&self, headers: &[Header]) -> bool {
+        headers.iter()
+            .find(|h| h.name.eq_ignore_ascii_case("List-Unsubscribe-Post"))
+            .map(|h| h.value.eq_ignore_ascii_case("List-Unsubscribe=One-Click"))
+            .unwrap_or(false)
+    }
+
     fn extract_unsubscribe_link(&self, headers: &[Header]) -> Option<String> {
         headers.iter()
             .find(|h| h.name == "List-Unsubscribe")
@@ -139,92 +257,351 @@ impl GmailClient {
             })
     }
 
-    fn extract_body(&self, payload: &Payload) -> Result<String, Box<dyn Error>> {
-        // First, try to get content from the main body
-        if let Some(content) = self.get_content_from_body(&payload.body) {
-            return Ok(content);
-        }
-
-        // If main body is empty, try to get content from parts
-        if let Some(parts) = &payload.parts {
-            return self.get_content_from_parts(parts);
-        }
-
-        Ok("No readable content found in the email.".to_string())
-    }
-
-    fn get_content_from_body(&self, body: &Body) -> Option<String> {
-        body.data.as_ref().and_then(|data| self.decode_and_render_body(data).ok())
+    fn extract_body(&self, payload: &Payload) -> String {
+        let mime_type = payload.mimeType.as_deref().unwrap_or("text/plain");
+        self.content_from(mime_type, &payload.body, &payload.headers, payload.parts.as_deref())
+            .unwrap_or_else(|| "No readable content found in the email.".to_string())
     }
 
-    fn get_content_from_parts(&self, parts: &[Part]) -> Result<String, Box<dyn Error>> {
-        let mut text_plain = String::new();
-        let mut text_html = String::new();
-
-        for part in parts {
-            match part.mimeType.as_str() {
-                "text/plain" => {
-                    if let Some(content) = self.get_content_from_body(&part.body) {
-                        text_plain.push_str(&content);
-                    }
-                }
-                "text/html" => {
-                    if let Some(content) = self.get_content_from_body(&part.body) {
-                        text_html.push_str(&content);
+    /// Walks a MIME tree choosing `text/plain` over `text/html` inside
+    /// `multipart/alternative`, and descending into `multipart/related`/`multipart/mixed`
+    /// (and similar container types) looking for the first renderable part.
+    fn content_from(&self, mime_type: &str, body: &Body, headers: &[Header], subparts: Option<&[Part]>) -> Option<String> {
+        match mime_type {
+            "multipart/alternative" => {
+                let subparts = subparts?;
+                if let Some(plain) = subparts.iter().find(|p| p.mimeType == "text/plain") {
+                    if let Some(content) = self.decode_part_body(plain) {
+                        return Some(content);
                     }
                 }
-                _ => {
-                    // For multipart types, recursively check their parts
-                    if let Some(subparts) = &part.parts {
-                        let content = self.get_content_from_parts(subparts)?;
-                        if !content.is_empty() {
-                            return Ok(content);
-                        }
+                if let Some(html) = subparts.iter().find(|p| p.mimeType == "text/html") {
+                    if let Some(content) = self.decode_part_body(html) {
+                        return Some(from_read(content.as_bytes(), 80));
                     }
                 }
+                subparts.iter().find_map(|p| self.content_from_part(p))
+            }
+            t if t.starts_with("multipart/") => {
+                subparts?.iter().find_map(|p| self.content_from_part(p))
             }
+            "text/html" => self.decode_body(body, headers).map(|html| from_read(html.as_bytes(), 80)),
+            t if t.starts_with("text/") => self.decode_body(body, headers),
+            // Anything else (images, PDFs, other binary attachments) isn't
+            // renderable content: returning `None` here lets the multipart
+            // walk above keep looking rather than treating a leading
+            // attachment's decoded bytes as the email body.
+            _ => None,
         }
+    }
 
-        // Prefer HTML content if available, otherwise use plain text
-        if !text_html.is_empty() {
-            Ok(from_read(text_html.as_bytes(), 80))
-        } else if !text_plain.is_empty() {
-            Ok(text_plain)
-        } else {
-            Ok("No readable content found in the email.".to_string())
+    fn content_from_part(&self, part: &Part) -> Option<String> {
+        self.content_from(&part.mimeType, &part.body, &part.headers, part.parts.as_deref())
+    }
+
+    /// Finds the original `text/html` part, decoded but *not* flattened by
+    /// `html2text`, for opening in an external browser (see `extract_body`,
+    /// which renders the same message as plain text for the inline view).
+    fn extract_html(&self, payload: &Payload) -> Option<String> {
+        let mime_type = payload.mimeType.as_deref().unwrap_or("text/plain");
+        self.html_from(mime_type, &payload.body, &payload.headers, payload.parts.as_deref())
+    }
+
+    fn html_from(&self, mime_type: &str, body: &Body, headers: &[Header], subparts: Option<&[Part]>) -> Option<String> {
+        match mime_type {
+            "text/html" => self.decode_body(body, headers),
+            t if t.starts_with("multipart/") => {
+                subparts?.iter().find_map(|p| self.html_from(&p.mimeType, &p.body, &p.headers, p.parts.as_deref()))
+            }
+            _ => None,
         }
     }
 
-    fn decode_and_render_body(&self, encoded_body: &str) -> Result<String, Box<dyn Error>> {
-        let decoded = general_purpose::STANDARD.decode(encoded_body.replace('-', "+").replace('_', "/"))?;
-        let body = String::from_utf8(decoded)
-            .map_err(|e| Box::new(e) as Box<dyn Error>)?;
-        
-        // Determine if the content is HTML or Markdown
-        if body.contains("&lt;") || body.contains("&gt;") || body.contains("&amp;") {
-            // Likely HTML content
-            Ok(from_read(body.as_bytes(), 80))
+    fn decode_part_body(&self, part: &Part) -> Option<String> {
+        self.decode_body(&part.body, &part.headers)
+    }
+
+    /// Decodes a part's body and transcodes it to UTF-8 using the `charset`
+    /// declared on its `Content-Type`. The Gmail API undoes the part's original
+    /// `Content-Transfer-Encoding` server-side and delivers `body.data` as
+    /// base64url, so only that base64url layer is decoded here.
+    fn decode_body(&self, body: &Body, headers: &[Header]) -> Option<String> {
+        let data = body.data.as_ref()?;
+        let raw = general_purpose::URL_SAFE_NO_PAD.decode(data.trim_end_matches('=')).ok()?;
+
+        let charset = header_value(headers, "Content-Type")
+            .and_then(|ct| parse_content_type(ct).1);
+        Some(decode_charset(&raw, charset.as_deref()))
+    }
+
+    /// Performs an RFC 8058 one-click unsubscribe POST against `url`. The spec requires
+    /// exactly this content type and body, with no redirects or confirmation step.
+    pub async fn one_click_unsubscribe(&self, url: &str) -> Result<(), Box<dyn Error>> {
+        // `self.client` follows redirects by default, which would violate the
+        // "no redirects" contract above, so this one request gets its own
+        // client with redirects disabled rather than reusing `self.client`.
+        let no_redirect_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        let response = no_redirect_client.post(url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body("List-Unsubscribe=One-Click")
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
         } else {
-            // Likely Markdown content
-            let parser = Parser::new(&body);
-            let mut html_output = String::new();
-            html::push_html(&mut html_output, parser);
-            Ok(from_read(html_output.as_bytes(), 80))
+            Err(format!("One-click unsubscribe request failed with status {}", response.status()).into())
         }
     }
 
-    pub async fn mark_as_read(&self, email_id: &str) -> Result<(), Box<dyn Error>> {
+    /// Adds and removes labels in one `/modify` call. This backs every mutation
+    /// exposed to the UI: read state, starring, archiving, trashing, and
+    /// arbitrary custom labels.
+    pub async fn modify_labels(&self, email_id: &str, add: &[&str], remove: &[&str]) -> Result<(), Box<dyn Error>> {
         let url = format!("https://www.googleapis.com/gmail/v1/users/me/messages/{}/modify", email_id);
         let body = serde_json::json!({
-            "removeLabelIds": ["UNREAD"]
+            "addLabelIds": add,
+            "removeLabelIds": remove,
         });
 
-        self.client.post(&url)
+        let response = self.client.post(&url)
             .bearer_auth(self.token.token().ok_or("No token available")?)
             .json(&body)
             .send()
             .await?;
 
-        Ok(())
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Modify labels request failed with status {}", response.status()).into())
+        }
+    }
+
+    pub async fn mark_as_read(&self, email_id: &str) -> Result<(), Box<dyn Error>> {
+        self.modify_labels(email_id, &[], &["UNREAD"]).await
+    }
+
+    /// Fetches the raw RFC822 bytes of a message (`format=raw`), used for a
+    /// faithful mbox export rather than reconstructing the message from
+    /// parsed MIME parts.
+    pub async fn fetch_raw(&self, id: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        let url = format!("https://www.googleapis.com/gmail/v1/users/me/messages/{}", id);
+        let response: serde_json::Value = self.client.get(&url)
+            .bearer_auth(self.token.token().ok_or("No token available")?)
+            .query(&[("format", "raw")])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let raw = response["raw"].as_str().ok_or("Message missing raw field")?;
+        let bytes = general_purpose::URL_SAFE_NO_PAD.decode(raw.trim_end_matches('='))?;
+        Ok(bytes)
+    }
+
+    /// Resolves a user-facing label name (e.g. "Work") to the Gmail label ID
+    /// `modify_labels` requires, via the `users/me/labels` endpoint.
+    pub async fn resolve_label_id(&self, name: &str) -> Result<String, Box<dyn Error>> {
+        let url = "https://www.googleapis.com/gmail/v1/users/me/labels";
+        let response: serde_json::Value = self.client.get(url)
+            .bearer_auth(self.token.token().ok_or("No token available")?)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let labels = response["labels"].as_array().ok_or("No labels found")?;
+        labels.iter()
+            .find(|label| {
+                label["name"].as_str()
+                    .map(|n| n.eq_ignore_ascii_case(name))
+                    .unwrap_or(false)
+            })
+            .and_then(|label| label["id"].as_str())
+            .map(|id| id.to_string())
+            .ok_or_else(|| format!("No label named '{}' found", name).into())
+    }
+}
+
+fn header_value<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers.iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Splits a `Content-Type` header value into its bare mime type and `charset` parameter.
+fn parse_content_type(value: &str) -> (String, Option<String>) {
+    let mut segments = value.split(';');
+    let mime_type = segments.next().unwrap_or("").trim().to_ascii_lowercase();
+    let charset = segments
+        .filter_map(|segment| {
+            let segment = segment.trim();
+            let (key, val) = segment.split_once('=')?;
+            if key.trim().eq_ignore_ascii_case("charset") {
+                Some(val.trim().trim_matches('"').to_string())
+            } else {
+                None
+            }
+        })
+        .next();
+    (mime_type, charset)
+}
+
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    let encoding = charset
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+/// Renders raw RFC822 messages as standard mbox entries for local export.
+pub struct MboxFormat;
+
+impl MboxFormat {
+    /// Formats one message as an mbox entry: a `From ` separator line built
+    /// from the message's own `From` header and its received timestamp, the
+    /// original headers verbatim, a blank line, then the body with
+    /// `>`-quoting of any body line that would otherwise look like a new
+    /// entry's separator. Line endings are normalized to LF throughout.
+    pub fn format_entry(raw: &[u8], received_at: Option<i64>) -> String {
+        let raw = String::from_utf8_lossy(raw).replace("\r\n", "\n");
+        let (headers, body) = raw.split_once("\n\n").unwrap_or((raw.as_str(), ""));
+
+        let mut entry = format!(
+            "From {} {}\n",
+            mbox_sender_address(headers),
+            mbox_date(received_at),
+        );
+        entry.push_str(headers);
+        entry.push_str("\n\n");
+        for line in body.lines() {
+            if line.starts_with("From ") {
+                entry.push('>');
+            }
+            entry.push_str(line);
+            entry.push('\n');
+        }
+        entry
+    }
+}
+
+/// Pulls the bare email address out of a raw header block's `From:` line,
+/// falling back to the conventional mbox placeholder when it's missing.
+fn mbox_sender_address(headers: &str) -> String {
+    let from = headers
+        .lines()
+        .find(|line| line.to_ascii_lowercase().starts_with("from:"))
+        .map(|line| line["from:".len()..].trim())
+        .unwrap_or("");
+
+    match (from.find('<'), from.find('>')) {
+        (Some(start), Some(end)) if start < end => from[start + 1..end].to_string(),
+        _ if !from.is_empty() => from.to_string(),
+        _ => "MAILER-DAEMON".to_string(),
+    }
+}
+
+/// Formats a Gmail `internalDate` (epoch milliseconds) as the `asctime`-style
+/// UTC timestamp mbox separator lines use, e.g. `Tue Jan  6 15:04:05 2026`.
+/// No chrono/time dependency exists in this project yet, so the calendar
+/// math is done by hand (Howard Hinnant's civil-from-days algorithm).
+fn mbox_date(received_at: Option<i64>) -> String {
+    let secs = received_at.unwrap_or(0) / 1000;
+    let days = secs.div_euclid(86_400);
+    let time_of_day = secs.rem_euclid(86_400);
+
+    let (year, month, day) = civil_from_days(days);
+    let hour = time_of_day / 3600;
+    let minute = (time_of_day % 3600) / 60;
+    let second = time_of_day % 60;
+
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"]; // day 0 = 1970-01-01, a Thursday
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let weekday = WEEKDAYS[days.rem_euclid(7) as usize];
+    let month_name = MONTHS[(month - 1) as usize];
+
+    format!(
+        "{} {} {:2} {:02}:{:02}:{:02} {}",
+        weekday, month_name, day, hour, minute, second, year
+    )
+}
+
+/// Converts a day count since the Unix epoch into a (year, month, day) civil
+/// date, per Howard Hinnant's public-domain `civil_from_days` algorithm.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as i64;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as i64;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_from_days_handles_epoch_and_leap_years() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        // 2000-03-01 is day 11017 since the epoch; 2000 is a leap year, so
+        // this exercises the Feb 29 -> Mar 1 month boundary.
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+    }
+
+    #[test]
+    fn mbox_date_formats_known_epoch_values() {
+        assert_eq!(mbox_date(Some(0)), "Thu Jan  1 00:00:00 1970");
+        assert_eq!(mbox_date(Some(-86_400_000)), "Wed Dec 31 00:00:00 1969");
+        assert_eq!(mbox_date(Some(951_868_800_000)), "Wed Mar  1 00:00:00 2000");
+        assert_eq!(mbox_date(None), "Thu Jan  1 00:00:00 1970");
+    }
+
+    #[test]
+    fn parse_content_type_splits_mime_and_charset() {
+        assert_eq!(
+            parse_content_type("text/html; charset=\"ISO-8859-1\""),
+            ("text/html".to_string(), Some("ISO-8859-1".to_string())),
+        );
+        assert_eq!(parse_content_type("text/plain"), ("text/plain".to_string(), None));
+    }
+
+    #[test]
+    fn decode_charset_transcodes_to_utf8() {
+        // 0xE9 is "é" in Windows-1252/ISO-8859-1 but not valid standalone UTF-8.
+        assert_eq!(decode_charset(&[0xE9], Some("windows-1252")), "é");
+        assert_eq!(decode_charset(b"hello", None), "hello");
+    }
+
+    #[test]
+    fn mbox_sender_address_extracts_bare_address() {
+        assert_eq!(
+            mbox_sender_address("From: Jane Doe <jane@example.com>\nSubject: Hi"),
+            "jane@example.com",
+        );
+        assert_eq!(mbox_sender_address("From: jane@example.com"), "jane@example.com");
+        assert_eq!(mbox_sender_address("Subject: no from header"), "MAILER-DAEMON");
+    }
+
+    #[test]
+    fn mbox_format_quotes_body_lines_starting_with_from() {
+        let raw = b"From: jane@example.com\nSubject: Hi\n\nFrom the desk of Jane\nRegards";
+        let entry = MboxFormat::format_entry(raw, Some(0));
+
+        assert!(entry.starts_with("From jane@example.com Thu Jan  1 00:00:00 1970\n"));
+        assert!(entry.contains("\n>From the desk of Jane\n"));
+        assert!(entry.contains("Regards\n"));
     }
 }
\ No newline at end of file
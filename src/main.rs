@@ -1,7 +1,9 @@
 use std::error::Error;
-use std::io;
+use std::fs;
+use std::io::{self, Write};
 use std::sync::Arc;
 use std::process::Command;
+use std::time::{Duration, Instant};
 
 use crossterm::{
     event::{self, Event, KeyCode},
@@ -19,16 +21,45 @@ use tui::{
 };
 use unicode_width::UnicodeWidthStr;
 
+mod config;
 mod gmail_api;
-use gmail_api::{Email, GmailClient};
+use config::Config;
+use gmail_api::{Email, FetchError, GmailClient, MboxFormat};
+
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How many HTML-viewer temp-file handles `App` keeps open at once. Older
+/// handles are dropped (and their tempfiles cleaned up) once this many more
+/// recent views have happened, bounding fd/disk usage over a long session
+/// instead of leaking one handle per `h` keypress.
+const MAX_OPEN_HTML_HANDLES: usize = 5;
 
 struct App {
     emails: Vec<Email>,
     current_index: usize,
     gmail_client: Arc<Mutex<GmailClient>>,
+    config: Config,
+    current_view: usize,
+    next_page_token: Option<String>,
+    is_online: bool,
+    last_error: Option<String>,
+    retry_deadline: Option<Instant>,
+    // HTML-viewer files, left on disk past the opener command's return: the
+    // external browser often delegates to an already-running instance and
+    // reads the file asynchronously, so unlinking it immediately can race the
+    // browser's own read. Bounded to `MAX_OPEN_HTML_HANDLES` so a long
+    // session doesn't leak one fd (or tempfile) per `h` keypress; the path is
+    // kept alongside the handle so eviction (and dropping `App`) can unlink
+    // the file, since dropping the `File` alone only closes the fd.
+    open_html_handles: Vec<(String, fs::File)>,
 }
 
 impl App {
+    /// Builds the app and its Gmail client but does not fetch anything yet;
+    /// the caller spawns `connect_with_retry` to populate `emails` in the
+    /// background so the TUI can show an "Offline — retrying" indicator
+    /// instead of blocking startup on a flaky connection.
     async fn new() -> Result<Self, Box<dyn Error>> {
         let secret = yup_oauth2::read_application_secret("client_secret.json").await?;
         let auth = yup_oauth2::InstalledFlowAuthenticator::builder(secret, yup_oauth2::InstalledFlowReturnMethod::HTTPRedirect)
@@ -39,22 +70,30 @@ impl App {
         let scopes = &["https://www.googleapis.com/auth/gmail.modify"];
         let token = auth.token(scopes).await?;
         let gmail_client = Arc::new(Mutex::new(GmailClient::new(token)));
-        
-        let emails = match gmail_client.lock().await.fetch_emails().await {
-            Ok(emails) => emails,
-            Err(e) => {
-                eprintln!("Error fetching emails: {}", e);
-                Vec::new()
-            }
+
+        let (config, config_error) = match Config::load("config.toml") {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(format!("config.toml: {}", e))),
         };
 
         Ok(Self {
-            emails,
+            emails: Vec::new(),
             current_index: 0,
             gmail_client,
+            config,
+            current_view: 0,
+            next_page_token: None,
+            is_online: false,
+            last_error: config_error,
+            retry_deadline: None,
+            open_html_handles: Vec::new(),
         })
     }
 
+    fn current_view_name(&self) -> &str {
+        self.config.views[self.current_view].name.as_str()
+    }
+
     async fn mark_as_read(&mut self) -> Result<(), Box<dyn Error>> {
         if let Some(email) = self.emails.get(self.current_index) {
             self.gmail_client
@@ -70,29 +109,109 @@ impl App {
         Ok(())
     }
 
+    async fn star(&mut self) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get_mut(self.current_index) {
+            self.gmail_client
+                .lock()
+                .await
+                .modify_labels(&email.id, &["STARRED"], &[])
+                .await?;
+            email.starred = true;
+            Ok("Email starred.".to_string())
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn unstar(&mut self) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get_mut(self.current_index) {
+            self.gmail_client
+                .lock()
+                .await
+                .modify_labels(&email.id, &[], &["STARRED"])
+                .await?;
+            email.starred = false;
+            Ok("Email unstarred.".to_string())
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn archive(&mut self) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get(self.current_index) {
+            self.gmail_client
+                .lock()
+                .await
+                .modify_labels(&email.id, &[], &["INBOX"])
+                .await?;
+            self.emails.remove(self.current_index);
+            if self.current_index >= self.emails.len() {
+                self.current_index = self.emails.len().saturating_sub(1);
+            }
+            Ok("Email archived.".to_string())
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn trash(&mut self) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get(self.current_index) {
+            self.gmail_client
+                .lock()
+                .await
+                .modify_labels(&email.id, &["TRASH"], &["INBOX"])
+                .await?;
+            self.emails.remove(self.current_index);
+            if self.current_index >= self.emails.len() {
+                self.current_index = self.emails.len().saturating_sub(1);
+            }
+            Ok("Email moved to trash.".to_string())
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn add_label(&mut self, name: &str) -> Result<String, Box<dyn Error>> {
+        if name.is_empty() {
+            return Ok("No label name entered.".to_string());
+        }
+        if let Some(email) = self.emails.get(self.current_index) {
+            let client = self.gmail_client.lock().await;
+            let label_id = client.resolve_label_id(name).await?;
+            client.modify_labels(&email.id, &[label_id.as_str()], &[]).await?;
+            Ok(format!("Added label '{}'.", name))
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn remove_label(&mut self, name: &str) -> Result<String, Box<dyn Error>> {
+        if name.is_empty() {
+            return Ok("No label name entered.".to_string());
+        }
+        if let Some(email) = self.emails.get(self.current_index) {
+            let client = self.gmail_client.lock().await;
+            let label_id = client.resolve_label_id(name).await?;
+            client.modify_labels(&email.id, &[], &[label_id.as_str()]).await?;
+            Ok(format!("Removed label '{}'.", name))
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
     async fn unsubscribe(&self) -> Result<String, Box<dyn Error>> {
         if let Some(email) = self.emails.get(self.current_index) {
             if let Some(link) = &email.unsubscribe_link {
-                if link.starts_with("http") {
-                    let (program, args) = if cfg!(target_os = "linux") {
-                        ("xdg-open", vec![link.as_str()])
-                    } else if cfg!(target_os = "macos") {
-                        ("open", vec![link.as_str()])
-                    } else if cfg!(target_os = "windows") {
-                        ("cmd", vec!["/C", "start", "", link.as_str()])
-                    } else {
-                        return Err("Unsupported operating system".into());
-                    };
-
-                    let status = Command::new(program)
-                        .args(&args)
-                        .status()?;
-
-                    if status.success() {
-                        Ok("Unsubscribe link opened.".to_string())
-                    } else {
-                        Err(format!("Failed to open unsubscribe link: {}", link).into())
-                    }
+                if email.unsubscribe_one_click && link.starts_with("https:") {
+                    self.gmail_client
+                        .lock()
+                        .await
+                        .one_click_unsubscribe(link)
+                        .await?;
+                    Ok("Unsubscribed with one click.".to_string())
+                } else if link.starts_with("http") {
+                    open_in_external_viewer(link)?;
+                    Ok("Unsubscribe link opened.".to_string())
                 } else if link.starts_with("mailto:") {
                     Ok(format!("This email uses a mailto link for unsubscribing. Please send an email to {}", &link[7..]))
                 } else {
@@ -105,6 +224,232 @@ impl App {
             Ok("No email selected.".to_string())
         }
     }
+
+    async fn export_current(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get(self.current_index) {
+            let raw = self.gmail_client.lock().await.fetch_raw(&email.id).await?;
+            let entry = MboxFormat::format_entry(&raw, email.received_at);
+            fs::write(path, entry)?;
+            Ok(format!("Exported 1 email to {}", path))
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+
+    async fn export_all(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        if self.emails.is_empty() {
+            return Ok("No emails to export.".to_string());
+        }
+
+        let client = self.gmail_client.lock().await;
+        let mut mbox = String::new();
+        for email in &self.emails {
+            let raw = client.fetch_raw(&email.id).await?;
+            mbox.push_str(&MboxFormat::format_entry(&raw, email.received_at));
+        }
+        fs::write(path, mbox)?;
+        Ok(format!("Exported {} emails to {}", self.emails.len(), path))
+    }
+
+    /// Opens the current email's original HTML in the user's browser, for the
+    /// rich marketing layouts that `html2text` flattens in the inline view.
+    async fn view_html(&mut self) -> Result<String, Box<dyn Error>> {
+        if let Some(email) = self.emails.get(self.current_index) {
+            match email.html_body.clone() {
+                Some(html) => {
+                    let (path, handle) = write_temp_html(&html)?;
+                    open_in_external_viewer(&path)?;
+                    self.open_html_handles.push((path, handle));
+                    if self.open_html_handles.len() > MAX_OPEN_HTML_HANDLES {
+                        let (evicted_path, _) = self.open_html_handles.remove(0);
+                        let _ = fs::remove_file(evicted_path);
+                    }
+                    Ok("Opened HTML version in browser.".to_string())
+                }
+                None => Ok("No HTML content available for this email.".to_string()),
+            }
+        } else {
+            Ok("No email selected.".to_string())
+        }
+    }
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        for (path, _) in self.open_html_handles.drain(..) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Advances to the next configured view and fetches its first page.
+///
+/// Takes `Arc<Mutex<App>>` rather than `&mut App` (unlike most `App`
+/// mutators) and only locks `App` to read/write state, never across the
+/// `GmailClient` network call — the same discipline `connect_with_retry`
+/// uses. Holding the `App` guard across that `.await` would deadlock against
+/// `connect_with_retry`'s own in-flight startup fetch, freezing the whole UI
+/// for the remainder of its retry/backoff.
+async fn switch_view(app: &Arc<Mutex<App>>) -> Result<String, Box<dyn Error>> {
+    let (client, query, page_size, sort) = {
+        let mut app = app.lock().await;
+        app.current_view = (app.current_view + 1) % app.config.views.len();
+        let view = &app.config.views[app.current_view];
+        (app.gmail_client.clone(), view.query.clone(), app.config.page_size, view.sort.clone())
+    };
+
+    let (emails, next_page_token) = client
+        .lock()
+        .await
+        .fetch_page(&query, page_size, None, sort.as_deref())
+        .await?;
+
+    let mut app = app.lock().await;
+    app.emails = emails;
+    app.current_index = 0;
+    app.next_page_token = next_page_token;
+    app.is_online = true;
+    app.last_error = None;
+    app.retry_deadline = None;
+    Ok(app.current_view_name().to_string())
+}
+
+/// Fetches and appends the next page for the current view, with the same
+/// lock discipline as `switch_view` above. Returns `false` when there is
+/// nothing more to load.
+async fn fetch_more(app: &Arc<Mutex<App>>) -> Result<bool, Box<dyn Error>> {
+    let (client, query, page_size, page_token, sort) = {
+        let app = app.lock().await;
+        let token = match &app.next_page_token {
+            Some(token) => token.clone(),
+            None => return Ok(false),
+        };
+        let view = &app.config.views[app.current_view];
+        (app.gmail_client.clone(), view.query.clone(), app.config.page_size, token, view.sort.clone())
+    };
+
+    let (mut emails, next_page_token) = client
+        .lock()
+        .await
+        .fetch_page(&query, page_size, Some(&page_token), sort.as_deref())
+        .await?;
+
+    let mut app = app.lock().await;
+    app.emails.append(&mut emails);
+    gmail_api::sort_emails(&mut app.emails, sort.as_deref());
+    app.next_page_token = next_page_token;
+    app.is_online = true;
+    app.last_error = None;
+    app.retry_deadline = None;
+    Ok(true)
+}
+
+/// Launches `target` (a URL or file path) with the platform's default opener.
+/// Shared by the unsubscribe-link and HTML-viewer actions.
+fn open_in_external_viewer(target: &str) -> Result<(), Box<dyn Error>> {
+    let (program, args) = if cfg!(target_os = "linux") {
+        ("xdg-open", vec![target])
+    } else if cfg!(target_os = "macos") {
+        ("open", vec![target])
+    } else if cfg!(target_os = "windows") {
+        ("cmd", vec!["/C", "start", "", target])
+    } else {
+        return Err("Unsupported operating system".into());
+    };
+
+    let status = Command::new(program).args(&args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to open {}", target).into())
+    }
+}
+
+/// Writes `html` to a temporary file for viewing in an external browser. The
+/// file is created owner-only (where the platform supports it), and the path
+/// includes a per-call counter (not just the pid) so opening a second
+/// email's HTML doesn't truncate the file a still-open browser tab from an
+/// earlier call may be reading. The file must stay on disk until the viewer
+/// process has had a chance to read it; dropping the returned `File` only
+/// closes the fd and leaves the file in place.
+///
+/// This intentionally does not use `memfd_create`'s `/proc/self/fd/N` trick:
+/// `xdg-open`/`open` commonly hand the path to an already-running browser
+/// instance over IPC rather than opening it themselves, and that instance
+/// never inherited our fd, so the path wouldn't resolve to our content in
+/// the most common desktop setup. A real (if owner-only, short-lived) file
+/// is the only option that works regardless of how the opener dispatches.
+fn write_temp_html(html: &str) -> io::Result<(String, fs::File)> {
+    static CALL_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let call_id = CALL_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+    let path = std::env::temp_dir().join(format!("gmail-cli-{}-{}.html", std::process::id(), call_id));
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+
+    file.write_all(html.as_bytes())?;
+    Ok((path.to_string_lossy().to_string(), file))
+}
+
+/// Fetches the current view's first page into `app`, retrying transient
+/// failures with exponential backoff (plus jitter) capped at 30s. Stops
+/// immediately on a fatal (auth) error instead of retrying forever.
+async fn connect_with_retry(app: Arc<Mutex<App>>) {
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+
+    loop {
+        let (client, query, page_size, sort) = {
+            let app = app.lock().await;
+            let view = &app.config.views[app.current_view];
+            (app.gmail_client.clone(), view.query.clone(), app.config.page_size, view.sort.clone())
+        };
+
+        match client.lock().await.fetch_page(&query, page_size, None, sort.as_deref()).await {
+            Ok((emails, next_page_token)) => {
+                let mut app = app.lock().await;
+                app.emails = emails;
+                app.next_page_token = next_page_token;
+                app.is_online = true;
+                app.last_error = None;
+                app.retry_deadline = None;
+                return;
+            }
+            Err(FetchError::Fatal(message)) => {
+                let mut app = app.lock().await;
+                app.is_online = false;
+                app.last_error = Some(message);
+                app.retry_deadline = None;
+                return;
+            }
+            Err(FetchError::Transient(message)) => {
+                let wait = jittered(backoff);
+                {
+                    let mut app = app.lock().await;
+                    app.is_online = false;
+                    app.last_error = Some(message);
+                    app.retry_deadline = Some(Instant::now() + wait);
+                }
+                tokio::time::sleep(wait).await;
+                backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Adds up to 250ms of jitter so simultaneous clients don't retry in lockstep.
+fn jittered(base: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    base + Duration::from_millis((nanos % 250) as u64)
 }
 
 struct ScrollableText<'a> {
@@ -166,10 +511,24 @@ impl<'a> Widget for ScrollableText<'a> {
     }
 }
 
+enum LabelPrompt {
+    Add,
+    Remove,
+}
+
+enum ExportPrompt {
+    Current,
+    All,
+}
+
 struct TerminalUI {
     app: Arc<Mutex<App>>,
     status_message: String,
     scroll_offset: usize,
+    label_prompt: Option<LabelPrompt>,
+    label_input: String,
+    export_prompt: Option<ExportPrompt>,
+    export_input: String,
 }
 
 impl TerminalUI {
@@ -178,10 +537,14 @@ impl TerminalUI {
             app,
             status_message: String::new(),
             scroll_offset: 0,
+            label_prompt: None,
+            label_input: String::new(),
+            export_prompt: None,
+            export_input: String::new(),
         }
     }
 
-    fn ui<B: Backend>(&self, f: &mut Frame<B>) {
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, app: &App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
@@ -192,14 +555,13 @@ impl TerminalUI {
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
             .split(chunks[0]);
 
-        let app = self.app.try_lock().expect("Failed to acquire app lock");
-
         // Render email list
         let emails: Vec<ListItem> = app
             .emails
             .iter()
             .map(|email| {
-                ListItem::new(vec![Spans::from(Span::raw(&email.subject))])
+                let prefix = if email.starred { "★ " } else { "" };
+                ListItem::new(vec![Spans::from(Span::raw(format!("{}{}", prefix, email.subject)))])
                     .style(Style::default().fg(Color::White))
             })
             .collect();
@@ -208,7 +570,7 @@ impl TerminalUI {
         state.select(Some(app.current_index));
 
         let emails = List::new(emails)
-            .block(Block::default().borders(Borders::ALL).title("Emails"))
+            .block(Block::default().borders(Borders::ALL).title(format!("Emails — {}", app.current_view_name())))
             .highlight_style(Style::default().bg(Color::LightGreen).add_modifier(Modifier::BOLD))
             .highlight_symbol(">> ");
 
@@ -230,7 +592,25 @@ impl TerminalUI {
 
         // Render status bar
         let status_bar_width = chunks[1].width as usize - 2; // Subtracting 2 for borders
-        let truncated_message = self.truncate_with_ellipsis(&self.status_message, status_bar_width);
+        let display_message = match (&self.label_prompt, &self.export_prompt) {
+            (Some(LabelPrompt::Add), _) => format!("Add label (Enter to confirm, Esc to cancel): {}", self.label_input),
+            (Some(LabelPrompt::Remove), _) => format!("Remove label (Enter to confirm, Esc to cancel): {}", self.label_input),
+            (None, Some(ExportPrompt::Current)) => format!("Export current email to (Enter to confirm, Esc to cancel): {}", self.export_input),
+            (None, Some(ExportPrompt::All)) => format!("Export all emails to (Enter to confirm, Esc to cancel): {}", self.export_input),
+            (None, None) if !app.is_online => match app.retry_deadline {
+                Some(deadline) => format!(
+                    "Offline — retrying in {}s ({})",
+                    deadline.saturating_duration_since(Instant::now()).as_secs(),
+                    app.last_error.as_deref().unwrap_or("connection error"),
+                ),
+                None => format!(
+                    "Offline: {}",
+                    app.last_error.as_deref().unwrap_or("not connected"),
+                ),
+            },
+            (None, None) => self.status_message.clone(),
+        };
+        let truncated_message = self.truncate_with_ellipsis(&display_message, status_bar_width);
         let status_bar = tui::widgets::Paragraph::new(truncated_message)
             .style(Style::default().fg(Color::White).bg(Color::Black))
             .block(Block::default().borders(Borders::ALL))
@@ -239,7 +619,7 @@ impl TerminalUI {
         f.render_widget(status_bar, chunks[1]);
 
         // Render controls
-        let controls = tui::widgets::Paragraph::new("Q: Quit | R: Mark as Read | U: Unsubscribe | ↑↓: Navigate | PgUp/PgDn: Scroll")
+        let controls = tui::widgets::Paragraph::new("Q: Quit | R: Read | U: Unsubscribe | H: View HTML | S/shift+S: Star/Unstar | A: Archive | T: Trash | L/shift+L: Add/Remove Label | E/shift+E: Export/Export All | ↑↓: Navigate | PgUp/PgDn: Scroll | V: View | N: Next Page")
             .style(Style::default().fg(Color::White).bg(Color::DarkGray));
 
         let control_area = Rect {
@@ -275,12 +655,58 @@ impl TerminalUI {
 
     async fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
         loop {
-            terminal.draw(|f| self.ui(f))?;
+            {
+                // Locked only for the duration of the draw: `connect_with_retry`
+                // genuinely contends for this lock (unlike the rest of the UI's
+                // prior single-task history), so a `try_lock().expect(...)` here
+                // would be a crash waiting to happen rather than dead code.
+                let app = self.app.lock().await;
+                terminal.draw(|f| self.ui(f, &app))?;
+            }
+
+            // Poll rather than block so the offline countdown and background
+            // fetch results keep redrawing even without user input.
+            if !event::poll(Duration::from_millis(200))? {
+                continue;
+            }
 
             if let Event::Key(key) = event::read()? {
-                let mut app = self.app.lock().await;
+                if self.label_prompt.is_some() {
+                    self.handle_label_prompt_key(key.code).await;
+                    continue;
+                }
+                if self.export_prompt.is_some() {
+                    self.handle_export_prompt_key(key.code).await;
+                    continue;
+                }
+
+                // `v` and `n` are handled without holding the `App` lock across
+                // their network call (see `switch_view`/`fetch_more` below), so
+                // they can't freeze the UI behind a `connect_with_retry` fetch
+                // that's already in flight.
                 match key.code {
                     KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char('v') => {
+                        match switch_view(&self.app).await {
+                            Ok(name) => self.status_message = format!("Switched to view: {}", name),
+                            Err(e) => self.status_message = format!("Error switching view: {}", e),
+                        }
+                        self.scroll_offset = 0;
+                        continue;
+                    }
+                    KeyCode::Char('n') => {
+                        match fetch_more(&self.app).await {
+                            Ok(true) => self.status_message = "Loaded next page.".to_string(),
+                            Ok(false) => self.status_message = "No more messages to load.".to_string(),
+                            Err(e) => self.status_message = format!("Error loading next page: {}", e),
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                let mut app = self.app.lock().await;
+                match key.code {
                     KeyCode::Up => {
                         if app.current_index > 0 {
                             app.current_index -= 1;
@@ -305,6 +731,12 @@ impl TerminalUI {
                             Err(e) => self.status_message = format!("Error unsubscribing: {}", e),
                         }
                     }
+                    KeyCode::Char('h') => {
+                        match app.view_html().await {
+                            Ok(message) => self.status_message = message,
+                            Err(e) => self.status_message = format!("Error opening HTML view: {}", e),
+                        }
+                    }
                     KeyCode::PageUp => {
                         if self.scroll_offset > 0 {
                             self.scroll_offset = self.scroll_offset.saturating_sub(10);
@@ -313,12 +745,119 @@ impl TerminalUI {
                     KeyCode::PageDown => {
                         self.scroll_offset += 10;
                     }
+                    KeyCode::Char('s') => {
+                        match app.star().await {
+                            Ok(message) => self.status_message = message,
+                            Err(e) => self.status_message = format!("Error starring email: {}", e),
+                        }
+                    }
+                    KeyCode::Char('S') => {
+                        match app.unstar().await {
+                            Ok(message) => self.status_message = message,
+                            Err(e) => self.status_message = format!("Error unstarring email: {}", e),
+                        }
+                    }
+                    KeyCode::Char('a') => {
+                        match app.archive().await {
+                            Ok(message) => self.status_message = message,
+                            Err(e) => self.status_message = format!("Error archiving email: {}", e),
+                        }
+                    }
+                    KeyCode::Char('t') => {
+                        match app.trash().await {
+                            Ok(message) => self.status_message = message,
+                            Err(e) => self.status_message = format!("Error trashing email: {}", e),
+                        }
+                    }
+                    KeyCode::Char('l') => {
+                        self.label_prompt = Some(LabelPrompt::Add);
+                    }
+                    KeyCode::Char('L') => {
+                        self.label_prompt = Some(LabelPrompt::Remove);
+                    }
+                    KeyCode::Char('e') => {
+                        self.export_prompt = Some(ExportPrompt::Current);
+                    }
+                    KeyCode::Char('E') => {
+                        self.export_prompt = Some(ExportPrompt::All);
+                    }
                     _ => {}
                 }
             }
         }
     }
 
+    async fn handle_label_prompt_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                let name = self.label_input.trim().to_string();
+                let prompt = self.label_prompt.take();
+                self.label_input.clear();
+
+                let mut app = self.app.lock().await;
+                let result = match prompt {
+                    Some(LabelPrompt::Add) => app.add_label(&name).await,
+                    Some(LabelPrompt::Remove) => app.remove_label(&name).await,
+                    None => Ok(String::new()),
+                };
+                match result {
+                    Ok(message) => self.status_message = message,
+                    Err(e) => self.status_message = format!("Error updating label: {}", e),
+                }
+            }
+            KeyCode::Esc => {
+                self.label_prompt = None;
+                self.label_input.clear();
+                self.status_message = "Cancelled.".to_string();
+            }
+            KeyCode::Backspace => {
+                self.label_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.label_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    async fn handle_export_prompt_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                let path = self.export_input.trim().to_string();
+                let prompt = self.export_prompt.take();
+                self.export_input.clear();
+
+                if path.is_empty() {
+                    self.status_message = "No export path entered.".to_string();
+                    return;
+                }
+
+                let app = self.app.lock().await;
+                let result = match prompt {
+                    Some(ExportPrompt::Current) => app.export_current(&path).await,
+                    Some(ExportPrompt::All) => app.export_all(&path).await,
+                    None => Ok(String::new()),
+                };
+                match result {
+                    Ok(message) => self.status_message = message,
+                    Err(e) => self.status_message = format!("Error exporting mbox: {}", e),
+                }
+            }
+            KeyCode::Esc => {
+                self.export_prompt = None;
+                self.export_input.clear();
+                self.status_message = "Cancelled.".to_string();
+            }
+            KeyCode::Backspace => {
+                self.export_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.export_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
     async fn run(&mut self) -> io::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -347,6 +886,7 @@ impl TerminalUI {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let app = Arc::new(Mutex::new(App::new().await?));
+    tokio::spawn(connect_with_retry(app.clone()));
     let mut ui = TerminalUI::new(app);
     ui.run().await?;
     Ok(())